@@ -0,0 +1,63 @@
+//! Read buffers that can grow on demand.
+
+/// A read buffer used by [`Bus`](crate::Bus) that can grow on demand.
+///
+/// Implemented for [`Vec<u8>`] (behind the `std` feature), which grows up to a configurable
+/// ceiling so callers don't have to size the buffer for the largest message they'll ever receive.
+/// Also implemented for fixed-size buffers (plain byte arrays, `&mut [u8]`), which can't grow past
+/// their initial size, for use on `no_std` targets.
+pub trait GrowableBuffer: AsRef<[u8]> + AsMut<[u8]> {
+	/// Attempt to grow the buffer to at least `min_len` bytes, without exceeding `max_len`.
+	///
+	/// Returns `true` if the buffer is at least `min_len` bytes long afterwards.
+	fn try_grow(&mut self, min_len: usize, max_len: usize) -> bool {
+		let _ = max_len;
+		self.as_ref().len() >= min_len
+	}
+}
+
+#[cfg(feature = "std")]
+impl GrowableBuffer for std::vec::Vec<u8> {
+	fn try_grow(&mut self, min_len: usize, max_len: usize) -> bool {
+		if self.len() >= min_len {
+			return true;
+		}
+		if min_len > max_len {
+			return false;
+		}
+		self.resize(min_len, 0);
+		true
+	}
+}
+
+impl<const N: usize> GrowableBuffer for [u8; N] {}
+
+impl GrowableBuffer for &mut [u8] {}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn test_vec_try_grow() {
+		let mut buffer: Vec<u8> = vec![0; 4];
+		assert!(buffer.try_grow(4, 16) == true);
+		assert!(buffer.len() == 4);
+
+		assert!(buffer.try_grow(8, 16) == true);
+		assert!(buffer.len() == 8);
+
+		// Can't grow past `max_len`, and must leave the buffer untouched when it gives up.
+		assert!(buffer.try_grow(32, 16) == false);
+		assert!(buffer.len() == 8);
+	}
+
+	#[test]
+	fn test_fixed_buffer_does_not_grow() {
+		let mut buffer = [0u8; 4];
+		assert!(buffer.try_grow(4, 16) == true);
+		assert!(buffer.try_grow(8, 16) == false);
+	}
+}
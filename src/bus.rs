@@ -1,19 +1,39 @@
-use std::time::{Duration, Instant};
+use core::time::Duration;
 
+use crate::buffer::GrowableBuffer;
 use crate::bytestuff;
 use crate::checksum::calculate_checksum;
-use crate::endian::{read_u16_le, write_u16_le};
+use crate::clock::Clock;
+use crate::endian::write_u16_le;
+use crate::framing::{self, HEADER_PREFIX, HEADER_SIZE, STATUS_HEADER_SIZE};
+use crate::instructions::{self, Instruction, StatusResponse, Value};
+use crate::transport::{ProtoRead, ProtoWrite};
 use crate::{ReadError, TransferError, WriteError};
 
-const HEADER_PREFIX: [u8; 4] = [0xFF, 0xFF, 0xFD, 0x00];
-const HEADER_SIZE: usize = 8;
-const STATUS_HEADER_SIZE: usize = 9;
+#[cfg(feature = "std")]
+use crate::clock::StdClock;
+
+/// The packet ID used to broadcast an instruction to all connected motors.
+const BROADCAST_ID: u8 = 0xFE;
+
+/// The per-device results of a Sync Read or Bulk Read instruction, keyed by packet ID.
+#[cfg(feature = "std")]
+pub type SyncReadResults<T, E> = Vec<(u8, Result<T, ReadError<E>>)>;
+
+/// The default ceiling [`Bus::new`] and [`Bus::with_buffer_sizes`] grow their read buffer to.
+///
+/// Dynamixel status responses are small in practice, but a misbehaving or malicious motor
+/// shouldn't be able to make us grow the read buffer without bound.
+const DEFAULT_MAX_READ_BUFFER_SIZE: usize = 4096;
 
 /// Dynamixel Protocol 2 communication bus.
-pub struct Bus<Stream, ReadBuffer, WriteBuffer> {
+pub struct Bus<Stream, ReadBuffer, WriteBuffer, C> {
 	/// The underlying stream (normally a serial port).
 	stream: Stream,
 
+	/// The clock used to time out reads.
+	clock: C,
+
 	/// The timeout for reading a single response.
 	read_timeout: Duration,
 
@@ -23,11 +43,15 @@ pub struct Bus<Stream, ReadBuffer, WriteBuffer> {
 	/// The total number of valid bytes in the read buffer.
 	read_len: usize,
 
+	/// The maximum size the read buffer is allowed to grow to.
+	max_read_buffer_size: usize,
+
 	/// The buffer for outgoing messages.
 	write_buffer: WriteBuffer,
 }
 
-impl<Stream> Bus<Stream, Vec<u8>, Vec<u8>>
+#[cfg(feature = "std")]
+impl<Stream> Bus<Stream, Vec<u8>, Vec<u8>, StdClock>
 where
 	Stream: std::io::Read + std::io::Write,
 {
@@ -38,31 +62,72 @@ where
 
 	/// Create a new bus with the specified sizes for the read and write buffers.
 	pub fn with_buffer_sizes(stream: Stream, read_timeout: Duration, read_buffer: usize, write_buffer: usize) -> Self {
-		Self::with_buffers(stream, read_timeout, vec![0; read_buffer], vec![0; write_buffer])
+		let mut bus = Self::with_buffers_and_clock(stream, StdClock, read_timeout, vec![0; read_buffer], vec![0; write_buffer]);
+		bus.set_max_read_buffer_size(DEFAULT_MAX_READ_BUFFER_SIZE.max(read_buffer));
+		bus
 	}
 }
 
-impl<Stream, ReadBuffer, WriteBuffer> Bus<Stream, ReadBuffer, WriteBuffer>
+impl<Stream, ReadBuffer, WriteBuffer, C, E> Bus<Stream, ReadBuffer, WriteBuffer, C>
 where
-	Stream: std::io::Read + std::io::Write,
-	ReadBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	Stream: ProtoRead<Error = E> + ProtoWrite<Error = E>,
+	ReadBuffer: GrowableBuffer,
 	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	C: Clock,
 {
-	/// Create a new bus using pre-allocated buffers.
-	pub fn with_buffers(stream: Stream, read_timeout: Duration, read_buffer: ReadBuffer, mut write_buffer: WriteBuffer) -> Self {
+	/// Create a new bus using pre-allocated buffers and the default clock for this platform.
+	#[cfg(feature = "std")]
+	pub fn with_buffers(stream: Stream, read_timeout: Duration, read_buffer: ReadBuffer, write_buffer: WriteBuffer) -> Bus<Stream, ReadBuffer, WriteBuffer, StdClock> {
+		Bus::with_buffers_and_clock(stream, StdClock, read_timeout, read_buffer, write_buffer)
+	}
+
+	/// Create a new bus using pre-allocated buffers and a custom [`Clock`].
+	///
+	/// This is the entry point for `no_std` targets, which don't have [`std::time::Instant`]
+	/// available to time out reads.
+	pub fn with_buffers_and_clock(stream: Stream, clock: C, read_timeout: Duration, read_buffer: ReadBuffer, mut write_buffer: WriteBuffer) -> Self {
 		// Pre-fill write buffer with the header prefix.
 		assert!(write_buffer.as_mut().len() >= HEADER_SIZE + 2);
 		write_buffer.as_mut()[..4].copy_from_slice(&HEADER_PREFIX);
 
+		let max_read_buffer_size = read_buffer.as_ref().len();
 		Self {
 			stream,
+			clock,
 			read_timeout,
 			read_buffer,
 			read_len: 0,
+			max_read_buffer_size,
 			write_buffer,
 		}
 	}
 
+	/// Set the maximum size the read buffer is allowed to grow to.
+	///
+	/// Once a status response declares a body length that would need the read buffer to grow
+	/// past this size, [`Self::read_status_response`] fails immediately with
+	/// [`ReadError::MessageTooLarge`] instead of waiting for the read timeout to expire.
+	///
+	/// Defaults to the initial size of the read buffer, i.e. no growth at all. [`Self::new`] and
+	/// [`Self::with_buffer_sizes`] raise this default to [`DEFAULT_MAX_READ_BUFFER_SIZE`].
+	pub fn set_max_read_buffer_size(&mut self, max_read_buffer_size: usize) {
+		self.max_read_buffer_size = max_read_buffer_size;
+	}
+
+	/// Execute a typed instruction and parse its response.
+	///
+	/// This is a thin wrapper around [`Self::transfer_single`] that encodes the instruction
+	/// parameters and parses the status response for you, so callers don't have to hand-encode
+	/// bytes or interpret [`Response::parameters`] themselves.
+	///
+	/// This is not suitable for broadcast instructions, for the same reason as [`Self::transfer_single`].
+	pub fn execute<I: Instruction>(&mut self, instruction: I) -> Result<I::Response, TransferError<E>> {
+		let response = self.transfer_single(instruction.packet_id(), instruction.instruction_id(), instruction.request_parameters_len(), |buffer| {
+			instruction.encode_parameters(buffer)
+		})?;
+		Ok(instruction.parse_response(&response)?)
+	}
+
 	/// Write a raw instruction to a stream, and read a single raw response.
 	///
 	/// This function also checks that the packet ID of the status response matches the one from the instruction.
@@ -76,7 +141,7 @@ where
 		instruction_id: u8,
 		parameter_count: usize,
 		encode_parameters: F,
-	) -> Result<Response<Stream, ReadBuffer, WriteBuffer>, TransferError>
+	) -> Result<Response<Stream, ReadBuffer, WriteBuffer, C>, TransferError<E>>
 	where
 		F: FnOnce(&mut [u8]),
 	{
@@ -87,13 +152,7 @@ where
 	}
 
 	/// Write an instruction message to the bus.
-	pub fn write_instruction<F>(
-		&mut self,
-		packet_id: u8,
-		instruction_id: u8,
-		parameter_count: usize,
-		encode_parameters: F,
-	) -> Result<(), WriteError>
+	pub fn write_instruction<F>(&mut self, packet_id: u8, instruction_id: u8, parameter_count: usize, encode_parameters: F) -> Result<(), WriteError<E>>
 	where
 		F: FnOnce(&mut [u8]),
 	{
@@ -103,11 +162,14 @@ where
 		// and read() can potentially read more than one reply per syscall.
 		self.read_len = 0;
 
-		let buffer = self.write_buffer.as_mut();
-		if buffer.len() < HEADER_SIZE + parameter_count + 2 {
-			// TODO: return proper error.
-			panic!("write buffer not large enough for outgoing mesage");
+		let required = HEADER_SIZE + parameter_count + 2;
+		let available = self.write_buffer.as_ref().len();
+		if available < required {
+			return Err(WriteError::BufferTooSmall { required, available });
 		}
+		let buffer = self.write_buffer.as_mut();
+		// Restore the header prefix: write_instruction_vectored puts the body at the start of the buffer.
+		buffer[..4].copy_from_slice(&HEADER_PREFIX);
 
 		// Add the header, with a placeholder for the length field.
 		buffer[4] = packet_id;
@@ -117,9 +179,10 @@ where
 		encode_parameters(&mut buffer[HEADER_SIZE..][..parameter_count]);
 
 		// Perform bitstuffing on the body.
-		// The header never needs stuffing.
-		// TODO: properly propagate error.
-		let stuffed_body_len = bytestuff::stuff_inplace(&mut buffer[HEADER_SIZE..], parameter_count).unwrap();
+		// The header never needs stuffing. Leave the last 2 bytes alone: they're reserved for the
+		// checksum, and stuff_inplace must not be allowed to grow the body into them.
+		let body_buffer_len = available - HEADER_SIZE - 2;
+		let stuffed_body_len = bytestuff::stuff_inplace(&mut buffer[HEADER_SIZE..][..body_buffer_len], parameter_count).map_err(|()| WriteError::StuffingFailed)?;
 
 		write_u16_le(&mut buffer[5..], stuffed_body_len as u16 + 3);
 
@@ -131,56 +194,49 @@ where
 		// Send message.
 		let stuffed_message = &buffer[..checksum_index + 2];
 		trace!("sending instruction: {:02X?}", stuffed_message);
-		self.stream.write_all(stuffed_message)?;
+		self.stream.write_all(stuffed_message).map_err(WriteError::Io)?;
 		Ok(())
 	}
 
 	/// Read a raw status response from the bus.
-	pub fn read_status_response(&mut self) -> Result<Response<Stream, ReadBuffer, WriteBuffer>, ReadError> {
-		let deadline = Instant::now() + self.read_timeout;
+	pub fn read_status_response(&mut self) -> Result<Response<Stream, ReadBuffer, WriteBuffer, C>, ReadError<E>> {
+		let deadline = self.clock.deadline(self.clock.now(), self.read_timeout);
+		let response = self.read_raw_response(deadline)?;
+		crate::InvalidInstruction::check(response.instruction_id(), instructions::instruction_id::STATUS)?;
+		crate::MotorError::check(response.error())?;
+		Ok(response)
+	}
+
+	/// Read a single raw status response from the bus, without checking the instruction ID or motor error field.
+	///
+	/// Used by [`Self::read_status_response`] and by the sync/bulk helpers, which need to inspect the
+	/// packet ID of a response before deciding whether it's one they're interested in.
+	fn read_raw_response(&mut self, deadline: C::Instant) -> Result<Response<Stream, ReadBuffer, WriteBuffer, C>, ReadError<E>> {
 		let stuffed_message_len = loop {
-			if Instant::now() > deadline {
-				return Err(std::io::ErrorKind::TimedOut.into());
-			}
-			// Try to read more data into the buffer.
-			let new_data = self.stream.read(&mut self.read_buffer.as_mut()[self.read_len..])?;
-			if new_data == 0 {
-				continue;
+			// Always try to parse a message out of what's already buffered before waiting on more
+			// I/O: a single read() can return more than one reply (e.g. several Sync Read replies
+			// back to back), and the rest must not sit there unparsed until another read() happens
+			// to bring in fresh bytes.
+			if let Some(message_len) = self.try_parse_buffered_message()? {
+				break message_len;
 			}
 
-			self.read_len += new_data;
-			self.remove_garbage();
-
-			let read_buffer = &self.read_buffer.as_mut()[..self.read_len];
-			if !read_buffer.starts_with(&HEADER_PREFIX) {
-				continue;
+			if self.clock.is_elapsed(deadline) {
+				return Err(ReadError::Timeout);
 			}
 
-			if self.read_len < STATUS_HEADER_SIZE {
-				continue;
-			}
-
-			let body_len = read_buffer[5] as usize + read_buffer[6] as usize * 256;
-			let body_len = body_len - 2; // Length includes instruction and error fields, which is already included in STATUS_HEADER_SIZE too.
-
-			if self.read_len >= STATUS_HEADER_SIZE + body_len {
-				break STATUS_HEADER_SIZE + body_len;
-			}
+			// Try to read more data into the buffer.
+			let new_data = self.stream.read(&mut self.read_buffer.as_mut()[self.read_len..]).map_err(ReadError::Io)?;
+			self.read_len += new_data;
 		};
 
 		let buffer = self.read_buffer.as_mut();
 		let parameters_end = stuffed_message_len - 2;
 		trace!("read packet: {:02X?}", &buffer[..parameters_end]);
 
-		let checksum_message = read_u16_le(&buffer[parameters_end..]);
-		let checksum_computed = calculate_checksum(0, &buffer[..parameters_end]);
-		if checksum_message != checksum_computed {
+		if let Err(e) = framing::verify_checksum(buffer, parameters_end) {
 			self.consume_read_bytes(stuffed_message_len);
-			return Err(crate::InvalidChecksum {
-				message: checksum_message,
-				computed: checksum_computed,
-			}
-			.into());
+			return Err(e.into());
 		}
 
 		// Remove byte-stuffing from the parameters.
@@ -193,21 +249,254 @@ where
 			parameter_count,
 		};
 
-		crate::InvalidInstruction::check(response.instruction_id(), crate::instructions::instruction_id::STATUS)?;
-		crate::MotorError::check(response.error())?;
 		Ok(response)
 	}
+
+	/// Try to parse a complete status message out of what's already buffered, without reading any
+	/// more data from the stream.
+	///
+	/// Returns `Ok(Some(len))` if a full (stuffed) message of `len` bytes is ready to be read out of
+	/// the read buffer. Returns `Ok(None)` if more data is needed before a message can be parsed,
+	/// growing the read buffer first if that's both necessary and possible.
+	fn try_parse_buffered_message(&mut self) -> Result<Option<usize>, ReadError<E>> {
+		self.remove_garbage();
+
+		let read_buffer = &self.read_buffer.as_ref()[..self.read_len];
+		if !read_buffer.starts_with(&HEADER_PREFIX) {
+			return Ok(None);
+		}
+
+		let message_len = match framing::status_message_len(read_buffer) {
+			Ok(Some(message_len)) => message_len,
+			// The header itself doesn't fit in the buffer yet: grow just enough to read it,
+			// or give up if we're already at the configured ceiling, rather than calling
+			// read() into an empty slice until the read timeout expires.
+			Ok(None) if self.read_len >= self.read_buffer.as_ref().len() => {
+				if !self.read_buffer.try_grow(STATUS_HEADER_SIZE, self.max_read_buffer_size) {
+					// We can't even hold the status header: drop everything buffered so
+					// far instead of leaving it there to be re-discovered (and re-rejected) by
+					// the very next call, which would otherwise busy-spin on an empty read().
+					self.consume_read_bytes(self.read_len);
+					return Err(ReadError::MessageTooLarge { declared_len: STATUS_HEADER_SIZE });
+				}
+				return Ok(None);
+			},
+			Ok(None) => return Ok(None),
+			Err(e) => {
+				self.consume_read_bytes(STATUS_HEADER_SIZE);
+				return Err(e.into());
+			},
+		};
+
+		if message_len > self.max_read_buffer_size {
+			self.consume_read_bytes(self.read_len);
+			return Err(ReadError::MessageTooLarge { declared_len: message_len });
+		}
+
+		if self.read_len >= message_len {
+			return Ok(Some(message_len));
+		}
+
+		if self.read_buffer.as_ref().len() < message_len && !self.read_buffer.try_grow(message_len, self.max_read_buffer_size) {
+			self.consume_read_bytes(self.read_len);
+			return Err(ReadError::MessageTooLarge { declared_len: message_len });
+		}
+
+		Ok(None)
+	}
+
+	/// Write a Sync Read instruction and collect the responses from each of the given motors.
+	///
+	/// Sync Read asks a list of motors for the same control table address and length in a single
+	/// broadcast instruction. Each motor answers individually, so one unresponsive or erroring motor
+	/// doesn't prevent the others from reporting their value: every ID in `ids` gets a result, either
+	/// the decoded value or the [`ReadError`] (timeout or motor error) that ID ran into.
+	#[cfg(feature = "std")]
+	pub fn sync_read<T: Value>(&mut self, ids: &[u8], address: u16) -> Result<SyncReadResults<T, E>, WriteError<E>> {
+		self.write_instruction(BROADCAST_ID, instructions::instruction_id::SYNC_READ, 4 + ids.len(), |buffer| {
+			write_u16_le(buffer, address);
+			write_u16_le(&mut buffer[2..], T::ENCODED_LEN as u16);
+			buffer[4..].copy_from_slice(ids);
+		})?;
+		Ok(self.collect_status_responses(ids.to_vec(), |response| {
+			let parameters = response.parameters();
+			crate::InvalidParameterCount::check(parameters.len(), T::ENCODED_LEN)?;
+			Ok(T::decode(parameters))
+		}))
+	}
+
+	/// Write a Sync Write instruction, writing a (possibly different) value to each of the given motors.
+	///
+	/// Motors don't send a status response to a Sync Write instruction.
+	pub fn sync_write<T: Value>(&mut self, address: u16, values: &[(u8, T)]) -> Result<(), WriteError<E>> {
+		let stride = 1 + T::ENCODED_LEN;
+		self.write_instruction(BROADCAST_ID, instructions::instruction_id::SYNC_WRITE, 4 + values.len() * stride, |buffer| {
+			write_u16_le(buffer, address);
+			write_u16_le(&mut buffer[2..], T::ENCODED_LEN as u16);
+			for (i, (id, value)) in values.iter().enumerate() {
+				let entry = &mut buffer[4 + i * stride..][..stride];
+				entry[0] = *id;
+				value.encode(&mut entry[1..]);
+			}
+		})
+	}
+
+	/// Write a Bulk Read instruction and collect the responses from each of the given motors.
+	///
+	/// Unlike [`Self::sync_read`], each motor in a Bulk Read can be asked for a different control
+	/// table address and length, given as `(packet_id, address, length)` tuples. As with `sync_read`,
+	/// every requested ID gets a result, so one unresponsive or erroring motor doesn't poison the rest.
+	#[cfg(feature = "std")]
+	pub fn bulk_read(&mut self, reads: &[(u8, u16, u16)]) -> Result<SyncReadResults<Vec<u8>, E>, WriteError<E>> {
+		self.write_instruction(BROADCAST_ID, instructions::instruction_id::BULK_READ, reads.len() * 5, |buffer| {
+			for (i, (id, address, length)) in reads.iter().enumerate() {
+				let entry = &mut buffer[i * 5..][..5];
+				entry[0] = *id;
+				write_u16_le(&mut entry[1..], *address);
+				write_u16_le(&mut entry[3..], *length);
+			}
+		})?;
+		let ids = reads.iter().map(|&(id, _, _)| id).collect();
+		Ok(self.collect_status_responses(ids, |response| Ok(response.parameters().to_vec())))
+	}
+
+	/// Write a Bulk Write instruction, writing a (possibly different sized) value to each of the given motors.
+	///
+	/// Each write is given as a `(packet_id, address, data)` tuple. Motors don't send a status response
+	/// to a Bulk Write instruction.
+	pub fn bulk_write(&mut self, writes: &[(u8, u16, &[u8])]) -> Result<(), WriteError<E>> {
+		let total_len: usize = writes.iter().map(|(_, _, data)| 5 + data.len()).sum();
+		self.write_instruction(BROADCAST_ID, instructions::instruction_id::BULK_WRITE, total_len, |buffer| {
+			let mut offset = 0;
+			for (id, address, data) in writes {
+				buffer[offset] = *id;
+				write_u16_le(&mut buffer[offset + 1..], *address);
+				write_u16_le(&mut buffer[offset + 3..], data.len() as u16);
+				buffer[offset + 5..][..data.len()].copy_from_slice(data);
+				offset += 5 + data.len();
+			}
+		})
+	}
+
+	/// Read status responses until every ID in `remaining` has replied or the read timeout passes.
+	///
+	/// Each reply is decoded with `decode` as soon as it comes in and paired with its packet ID, so
+	/// that an error for one motor (a timeout or a [`crate::MotorError`]) doesn't discard the replies
+	/// already collected from the others. Replies from IDs that weren't expected are ignored, as are
+	/// malformed messages: we just keep reading until the deadline.
+	#[cfg(feature = "std")]
+	fn collect_status_responses<T>(
+		&mut self,
+		mut remaining: Vec<u8>,
+		mut decode: impl FnMut(&Response<Stream, ReadBuffer, WriteBuffer, C>) -> Result<T, ReadError<E>>,
+	) -> Vec<(u8, Result<T, ReadError<E>>)> {
+		let deadline = self.clock.deadline(self.clock.now(), self.read_timeout);
+		let mut results = Vec::with_capacity(remaining.len());
+		while !remaining.is_empty() {
+			if self.clock.is_elapsed(deadline) {
+				for id in remaining.drain(..) {
+					results.push((id, Err(ReadError::Timeout)));
+				}
+				break;
+			}
+
+			let response = match self.read_raw_response(deadline) {
+				Ok(response) => response,
+				Err(_) => continue,
+			};
+
+			let id = response.packet_id();
+			let Some(pos) = remaining.iter().position(|&remaining_id| remaining_id == id) else {
+				continue;
+			};
+			remaining.remove(pos);
+
+			let result = match crate::MotorError::check(response.error()) {
+				Ok(()) => decode(&response),
+				Err(e) => Err(ReadError::from(e)),
+			};
+			results.push((id, result));
+		}
+		results
+	}
 }
 
-impl<Stream, ReadBuffer, WriteBuffer> Bus<Stream, ReadBuffer, WriteBuffer>
+#[cfg(feature = "std")]
+impl<Stream, ReadBuffer, WriteBuffer, C> Bus<Stream, ReadBuffer, WriteBuffer, C>
 where
-	ReadBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	Stream: std::io::Read + std::io::Write,
+	ReadBuffer: GrowableBuffer,
+	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	C: Clock,
+{
+	/// Write an instruction message to the bus using a vectored write.
+	///
+	/// This works like [`Self::write_instruction`], except the write buffer only needs to hold the
+	/// (possibly stuffed) parameters and the checksum, not the whole message: the 8-byte header is
+	/// kept in a small on-stack scratch buffer instead, and sent together with the body in a single
+	/// [`write_vectored`](std::io::Write::write_vectored) call. This is useful when sending large
+	/// parameter payloads, since callers no longer need a write buffer sized for the whole message.
+	pub fn write_instruction_vectored<F>(&mut self, packet_id: u8, instruction_id: u8, parameter_count: usize, encode_parameters: F) -> Result<(), WriteError<std::io::Error>>
+	where
+		F: FnOnce(&mut [u8]),
+	{
+		self.read_len = 0;
+
+		let required = parameter_count + 2;
+		let available = self.write_buffer.as_ref().len();
+		if available < required {
+			return Err(WriteError::BufferTooSmall { required, available });
+		}
+		let buffer = self.write_buffer.as_mut();
+		encode_parameters(&mut buffer[..parameter_count]);
+
+		// Perform bitstuffing on the body. Leave the last 2 bytes alone: they're reserved for the
+		// checksum, and stuff_inplace must not be allowed to grow the body into them.
+		let body_buffer_len = available - 2;
+		let stuffed_body_len = bytestuff::stuff_inplace(&mut buffer[..body_buffer_len], parameter_count).map_err(|()| WriteError::StuffingFailed)?;
+
+		let mut header = [0u8; HEADER_SIZE];
+		header[..4].copy_from_slice(&HEADER_PREFIX);
+		header[4] = packet_id;
+		write_u16_le(&mut header[5..], stuffed_body_len as u16 + 3);
+		header[7] = instruction_id;
+
+		// Add the checksum, computed as a running checksum over the header and the stuffed body.
+		let checksum_index = stuffed_body_len;
+		let checksum = calculate_checksum(calculate_checksum(0, &header), &buffer[..checksum_index]);
+		write_u16_le(&mut buffer[checksum_index..], checksum);
+
+		let body = &buffer[..checksum_index + 2];
+		trace!("sending instruction (vectored): header={:02X?}, body={:02X?}", header, body);
+		let mut slices = [std::io::IoSlice::new(&header), std::io::IoSlice::new(body)];
+		write_vectored_all(&mut self.stream, &mut slices).map_err(WriteError::Io)?;
+		Ok(())
+	}
+}
+
+/// Write all of `slices` to `stream`, looping over [`write_vectored`](std::io::Write::write_vectored)
+/// since it's not guaranteed to consume every slice in one call.
+#[cfg(feature = "std")]
+fn write_vectored_all<Stream: std::io::Write>(stream: &mut Stream, mut slices: &mut [std::io::IoSlice]) -> std::io::Result<()> {
+	while !slices.is_empty() {
+		let written = stream.write_vectored(slices)?;
+		if written == 0 {
+			return Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "failed to write whole message"));
+		}
+		std::io::IoSlice::advance_slices(&mut slices, written);
+	}
+	Ok(())
+}
+
+impl<Stream, ReadBuffer, WriteBuffer, C> Bus<Stream, ReadBuffer, WriteBuffer, C>
+where
+	ReadBuffer: GrowableBuffer,
 	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
 {
 	/// Remove leading garbage data from the read buffer.
 	fn remove_garbage(&mut self) {
 		let read_buffer = self.read_buffer.as_mut();
-		let garbage_len = find_header(&read_buffer[..self.read_len]);
+		let garbage_len = framing::find_header(&read_buffer[..self.read_len]);
 		#[cfg(feature = "log")]
 		if garbage_len > 0 {
 			log::debug!("Skipping {} bytes of leading garbage.", garbage_len);
@@ -226,13 +515,13 @@ where
 /// A status response that is currently in the read buffer of a bus.
 ///
 /// When dropped, the response data is removed from the read buffer.
-pub struct Response<'a, Stream, ReadBuffer, WriteBuffer>
+pub struct Response<'a, Stream, ReadBuffer, WriteBuffer, C>
 where
-	ReadBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	ReadBuffer: GrowableBuffer,
 	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
 {
 	/// The bus that read the message.
-	bus: &'a mut Bus<Stream, ReadBuffer, WriteBuffer>,
+	bus: &'a mut Bus<Stream, ReadBuffer, WriteBuffer, C>,
 
 	/// The total length of the stuffed message.
 	stuffed_message_len: usize,
@@ -241,9 +530,9 @@ where
 	parameter_count: usize,
 }
 
-impl<'a, Stream, ReadBuffer, WriteBuffer> Response<'a, Stream, ReadBuffer, WriteBuffer>
+impl<'a, Stream, ReadBuffer, WriteBuffer, C> Response<'a, Stream, ReadBuffer, WriteBuffer, C>
 where
-	ReadBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	ReadBuffer: GrowableBuffer,
 	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
 {
 	/// Get the raw bytes of the message.
@@ -275,52 +564,156 @@ where
 	}
 }
 
-impl<'a, Stream, ReadBuffer, WriteBuffer> Drop for Response<'a, Stream, ReadBuffer, WriteBuffer>
+impl<'a, Stream, ReadBuffer, WriteBuffer, C> StatusResponse for Response<'a, Stream, ReadBuffer, WriteBuffer, C>
 where
-	ReadBuffer: AsRef<[u8]> + AsMut<[u8]>,
+	ReadBuffer: GrowableBuffer,
 	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
 {
-	fn drop(&mut self) {
-		self.bus.consume_read_bytes(self.stuffed_message_len);
+	fn packet_id(&self) -> u8 {
+		Response::packet_id(self)
 	}
-}
 
-/// Find the potential starting position of a header.
-///
-/// This will return the first possible position of the header prefix.
-/// Note that if the buffer ends with a partial header prefix,
-/// the start position of the partial header prefix is returned.
-fn find_header(buffer: &[u8]) -> usize {
-	for i in 0..buffer.len() {
-		let possible_prefix = HEADER_PREFIX.len().min(buffer.len() - i);
-		if buffer[i..].starts_with(&HEADER_PREFIX[..possible_prefix]) {
-			return i;
-		}
+	fn instruction_id(&self) -> u8 {
+		Response::instruction_id(self)
+	}
+
+	fn error(&self) -> u8 {
+		Response::error(self)
+	}
+
+	fn parameters(&self) -> &[u8] {
+		Response::parameters(self)
 	}
+}
 
-	buffer.len()
+impl<'a, Stream, ReadBuffer, WriteBuffer, C> Drop for Response<'a, Stream, ReadBuffer, WriteBuffer, C>
+where
+	ReadBuffer: GrowableBuffer,
+	WriteBuffer: AsRef<[u8]> + AsMut<[u8]>,
+{
+	fn drop(&mut self) {
+		self.bus.consume_read_bytes(self.stuffed_message_len);
+	}
 }
 
+
 #[cfg(test)]
+#[cfg(feature = "std")]
 mod test {
-	use super::*;
+	use std::io::Cursor;
+
 	use assert2::assert;
 
+	use super::*;
+
+	fn test_bus(write_buffer_len: usize) -> Bus<Cursor<Vec<u8>>, Vec<u8>, Vec<u8>, StdClock> {
+		Bus::with_buffers_and_clock(Cursor::new(Vec::new()), StdClock, Duration::from_millis(10), vec![0; 64], vec![0; write_buffer_len])
+	}
+
+	/// Build the raw bytes of an (unstuffed) status response, as a motor would send it.
+	fn encode_status_response(packet_id: u8, error: u8, parameters: &[u8]) -> Vec<u8> {
+		let declared_len = parameters.len() as u16 + 4; // instruction + error + parameters + checksum
+		let mut message = vec![0xFF, 0xFF, 0xFD, 0x00, packet_id, 0, 0, instructions::instruction_id::STATUS, error];
+		write_u16_le(&mut message[5..], declared_len);
+		message.extend_from_slice(parameters);
+		let checksum = calculate_checksum(0, &message);
+		message.extend_from_slice(&checksum.to_le_bytes());
+		message
+	}
+
+	#[test]
+	fn test_write_instruction_buffer_too_small() {
+		let mut bus = test_bus(HEADER_SIZE + 4); // enough to construct, not enough for a 4-byte payload
+		let result = bus.write_instruction(1, 2, 4, |buffer| buffer.copy_from_slice(&[1, 2, 3, 4]));
+		assert!(let Err(WriteError::BufferTooSmall { required: 14, available: 12 }) = result);
+	}
+
+	#[test]
+	fn test_write_instruction_stuffing_failed() {
+		// Exactly enough room for the unstuffed message, but stuffing the body grows it by one byte.
+		let mut bus = test_bus(HEADER_SIZE + 3 + 2);
+		let result = bus.write_instruction(1, 2, 3, |buffer| buffer.copy_from_slice(&[0xFF, 0xFF, 0xFD]));
+		assert!(let Err(WriteError::StuffingFailed) = result);
+	}
+
+	#[test]
+	fn test_write_instruction_vectored_matches_write_instruction() {
+		let mut bus_a = test_bus(64);
+		bus_a.write_instruction(5, 2, 4, |buffer| buffer.copy_from_slice(&[0xFF, 0xFF, 0xFD, 9])).unwrap();
+
+		let mut bus_b = test_bus(64);
+		bus_b.write_instruction_vectored(5, 2, 4, |buffer| buffer.copy_from_slice(&[0xFF, 0xFF, 0xFD, 9])).unwrap();
+
+		assert!(bus_a.stream.get_ref() == bus_b.stream.get_ref());
+	}
+
+	#[test]
+	fn test_read_status_response_message_too_large() {
+		// A status header declaring a body far larger than the configured read buffer ceiling.
+		let header = vec![0xFF, 0xFF, 0xFD, 0x00, 1, 0xFF, 0xFF, 0x55, 0];
+		let mut bus = Bus::with_buffer_sizes(Cursor::new(header), Duration::from_millis(50), 16, 16);
+		let result = bus.read_status_response();
+		assert!(let Err(ReadError::MessageTooLarge { .. }) = result);
+	}
+
+	#[test]
+	fn test_read_status_response_message_too_large_does_not_leave_bytes_buffered() {
+		// Same oversized header as above, but read twice on the same bus without an intervening
+		// write_instruction (the only other thing that resets read_len): the first call must drop
+		// the rejected header from the read buffer, so the second call finds nothing buffered and
+		// nothing left to read, and times out instead of reporting the stale header again.
+		let header = vec![0xFF, 0xFF, 0xFD, 0x00, 1, 0xFF, 0xFF, 0x55, 0];
+		let mut bus = Bus::with_buffer_sizes(Cursor::new(header), Duration::from_millis(50), 16, 16);
+		assert!(let Err(ReadError::MessageTooLarge { .. }) = bus.read_status_response());
+		assert!(let Err(ReadError::Timeout) = bus.read_status_response());
+	}
+
+	/// [`Bus::sync_read`] and [`Bus::bulk_read`] are thin wrappers around
+	/// [`Bus::collect_status_responses`]: exercise that directly against a [`Cursor`] preloaded with
+	/// synthetic replies, since driving it through `write_instruction` would overwrite the very same
+	/// buffer we're using to preload the replies.
+	#[test]
+	fn test_collect_status_responses_partial_and_out_of_order() {
+		// Replies for IDs 3 and 2 arrive out of order; ID 1 never replies; the reply from
+		// unrequested ID 9 must be ignored rather than matched to one of the requested IDs.
+		let mut data = Vec::new();
+		data.extend(encode_status_response(9, 0, &[0xAA]));
+		data.extend(encode_status_response(3, 0, &[30]));
+		data.extend(encode_status_response(2, 0, &[20]));
+
+		let mut bus = Bus::with_buffer_sizes(Cursor::new(data), Duration::from_millis(50), 64, 64);
+		let mut results = bus.collect_status_responses(vec![1, 2, 3], |response| {
+			let parameters = response.parameters();
+			crate::InvalidParameterCount::check(parameters.len(), 1)?;
+			Ok(parameters[0])
+		});
+		results.sort_by_key(|(id, _)| *id);
+
+		assert!(results.len() == 3);
+		assert!(results[0].0 == 1);
+		assert!(let Err(ReadError::Timeout) = &results[0].1);
+		assert!(results[1].0 == 2);
+		assert!(let Ok(20) = &results[1].1);
+		assert!(results[2].0 == 3);
+		assert!(let Ok(30) = &results[2].1);
+	}
+
 	#[test]
-	fn test_find_garbage_end() {
-		assert!(find_header(&[0xFF]) == 0);
-		assert!(find_header(&[0xFF, 0xFF]) == 0);
-		assert!(find_header(&[0xFF, 0xFF, 0xFD]) == 0);
-		assert!(find_header(&[0xFF, 0xFF, 0xFD, 0x00]) == 0);
-		assert!(find_header(&[0xFF, 0xFF, 0xFD, 0x00, 9]) == 0);
-
-		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF]) == 5);
-		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF]) == 5);
-		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF, 0xFD]) == 5);
-		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF, 0xFD, 0x00]) == 5);
-		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF, 0xFD, 0x00, 9]) == 5);
-
-		assert!(find_header(&[0xFF, 1]) == 2);
-		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 6]) == 7);
+	fn test_collect_status_responses_motor_error_isolated() {
+		// A MotorError reply for one ID must not prevent the others from reporting their value.
+		let mut data = Vec::new();
+		data.extend(encode_status_response(1, 0x01, &[]));
+		data.extend(encode_status_response(2, 0, &[42]));
+
+		let mut bus = Bus::with_buffer_sizes(Cursor::new(data), Duration::from_millis(50), 64, 64);
+		let mut results = bus.collect_status_responses(vec![1, 2], |response| {
+			let parameters = response.parameters();
+			crate::InvalidParameterCount::check(parameters.len(), 1)?;
+			Ok(parameters[0])
+		});
+		results.sort_by_key(|(id, _)| *id);
+
+		assert!(let Err(ReadError::MotorError(_)) = &results[0].1);
+		assert!(let Ok(42) = &results[1].1);
 	}
 }
@@ -0,0 +1,485 @@
+//! Async bus variant built on `tokio`'s [`AsyncRead`]/[`AsyncWrite`] traits.
+//!
+//! This mirrors [`Bus`](crate::Bus), but `await`s reads and writes instead of blocking the
+//! current thread. It reuses the framing helpers in [`crate::framing`] so the two paths can't
+//! drift apart, and times out reads with [`tokio::time::timeout`] instead of going through a
+//! [`Clock`](crate::Clock): `tokio`'s own timer already does that job for an async bus.
+//!
+//! Unlike [`Bus`], which is generic over the transport's error type, [`AsyncBus`] is concrete
+//! over [`std::io::Error`], since that's what [`AsyncRead`]/[`AsyncWrite`] streams use.
+
+use core::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::buffer::GrowableBuffer;
+use crate::bytestuff;
+use crate::checksum::calculate_checksum;
+use crate::endian::write_u16_le;
+use crate::framing::{self, HEADER_PREFIX, HEADER_SIZE, STATUS_HEADER_SIZE};
+use crate::instructions::{self, Instruction, StatusResponse};
+use crate::{ReadError, TransferError, WriteError};
+
+/// The default ceiling [`AsyncBus::new`] and [`AsyncBus::with_buffer_sizes`] grow their read buffer to.
+const DEFAULT_MAX_READ_BUFFER_SIZE: usize = 4096;
+
+/// Dynamixel Protocol 2 communication bus, built on `tokio`'s async I/O traits.
+pub struct AsyncBus<Stream> {
+	/// The underlying stream (normally a serial port or a socket).
+	stream: Stream,
+
+	/// The timeout for reading a single response.
+	read_timeout: Duration,
+
+	/// The buffer for reading incoming messages.
+	read_buffer: Vec<u8>,
+
+	/// The total number of valid bytes in the read buffer.
+	read_len: usize,
+
+	/// The maximum size the read buffer is allowed to grow to.
+	max_read_buffer_size: usize,
+
+	/// The buffer for outgoing messages.
+	write_buffer: Vec<u8>,
+}
+
+impl<Stream> AsyncBus<Stream>
+where
+	Stream: AsyncRead + AsyncWrite + Unpin,
+{
+	/// Create a new bus with 128 byte read and write buffers.
+	pub fn new(stream: Stream, read_timeout: Duration) -> Self {
+		Self::with_buffer_sizes(stream, read_timeout, 128, 128)
+	}
+
+	/// Create a new bus with the specified sizes for the read and write buffers.
+	pub fn with_buffer_sizes(stream: Stream, read_timeout: Duration, read_buffer: usize, write_buffer: usize) -> Self {
+		let mut bus = Self::with_buffers(stream, read_timeout, vec![0; read_buffer], vec![0; write_buffer]);
+		bus.set_max_read_buffer_size(DEFAULT_MAX_READ_BUFFER_SIZE.max(read_buffer));
+		bus
+	}
+
+	/// Create a new bus using pre-allocated buffers.
+	pub fn with_buffers(stream: Stream, read_timeout: Duration, read_buffer: Vec<u8>, mut write_buffer: Vec<u8>) -> Self {
+		// Pre-fill write buffer with the header prefix.
+		assert!(write_buffer.len() >= HEADER_SIZE + 2);
+		write_buffer[..4].copy_from_slice(&HEADER_PREFIX);
+
+		let max_read_buffer_size = read_buffer.len();
+		Self {
+			stream,
+			read_timeout,
+			read_buffer,
+			read_len: 0,
+			max_read_buffer_size,
+			write_buffer,
+		}
+	}
+
+	/// Set the maximum size the read buffer is allowed to grow to.
+	///
+	/// See [`Bus::set_max_read_buffer_size`](crate::Bus::set_max_read_buffer_size) for details.
+	pub fn set_max_read_buffer_size(&mut self, max_read_buffer_size: usize) {
+		self.max_read_buffer_size = max_read_buffer_size;
+	}
+
+	/// Execute a typed instruction and parse its response.
+	///
+	/// This is a thin wrapper around [`Self::transfer_single`] that encodes the instruction
+	/// parameters and parses the status response for you, so callers don't have to hand-encode
+	/// bytes or interpret [`AsyncResponse::parameters`] themselves.
+	///
+	/// This is not suitable for broadcast instructions, for the same reason as [`Self::transfer_single`].
+	pub async fn execute<I: Instruction>(&mut self, instruction: I) -> Result<I::Response, TransferError<std::io::Error>> {
+		let response = self
+			.transfer_single(instruction.packet_id(), instruction.instruction_id(), instruction.request_parameters_len(), |buffer| {
+				instruction.encode_parameters(buffer)
+			})
+			.await?;
+		Ok(instruction.parse_response(&response)?)
+	}
+
+	/// Write a raw instruction to a stream, and read a single raw response.
+	///
+	/// This function also checks that the packet ID of the status response matches the one from the instruction.
+	///
+	/// This is not suitable for broadcast instructions.
+	/// For broadcast instructions, each motor sends an individual response or no response is send at all.
+	/// Instead, use [`Self::write_instruction`] and [`Self::read_status_response`].
+	pub async fn transfer_single<F>(&mut self, packet_id: u8, instruction_id: u8, parameter_count: usize, encode_parameters: F) -> Result<AsyncResponse<Stream>, TransferError<std::io::Error>>
+	where
+		F: FnOnce(&mut [u8]),
+	{
+		self.write_instruction(packet_id, instruction_id, parameter_count, encode_parameters).await?;
+		let response = self.read_status_response().await?;
+		crate::error::InvalidPacketId::check(response.packet_id(), packet_id).map_err(ReadError::from)?;
+		Ok(response)
+	}
+
+	/// Write an instruction message to the bus.
+	pub async fn write_instruction<F>(&mut self, packet_id: u8, instruction_id: u8, parameter_count: usize, encode_parameters: F) -> Result<(), WriteError<std::io::Error>>
+	where
+		F: FnOnce(&mut [u8]),
+	{
+		// Throw away old data in the read buffer.
+		// Ideally, we would also flush the kernel buffer, but tokio doesn't expose that.
+		// We don't do this when reading a reply, because we might get multiple replies for one instruction,
+		// and read() can potentially read more than one reply per syscall.
+		self.read_len = 0;
+
+		let required = HEADER_SIZE + parameter_count + 2;
+		if self.write_buffer.len() < required {
+			return Err(WriteError::BufferTooSmall {
+				required,
+				available: self.write_buffer.len(),
+			});
+		}
+
+		let buffer = &mut self.write_buffer[..];
+
+		// Add the header, with a placeholder for the length field.
+		buffer[4] = packet_id;
+		buffer[5] = 0;
+		buffer[6] = 0;
+		buffer[7] = instruction_id;
+		encode_parameters(&mut buffer[HEADER_SIZE..][..parameter_count]);
+
+		// Perform bitstuffing on the body.
+		// The header never needs stuffing. Leave the last 2 bytes alone: they're reserved for the
+		// checksum, and stuff_inplace must not be allowed to grow the body into them.
+		let body_buffer_len = buffer.len() - HEADER_SIZE - 2;
+		let stuffed_body_len = bytestuff::stuff_inplace(&mut buffer[HEADER_SIZE..][..body_buffer_len], parameter_count).map_err(|()| WriteError::StuffingFailed)?;
+
+		write_u16_le(&mut buffer[5..], stuffed_body_len as u16 + 3);
+
+		// Add checksum.
+		let checksum_index = HEADER_SIZE + stuffed_body_len;
+		let checksum = calculate_checksum(0, &buffer[..checksum_index]);
+		write_u16_le(&mut buffer[checksum_index..], checksum);
+
+		// Send message.
+		let stuffed_message = &self.write_buffer[..checksum_index + 2];
+		trace!("sending instruction: {:02X?}", stuffed_message);
+		self.stream.write_all(stuffed_message).await.map_err(WriteError::Io)?;
+		Ok(())
+	}
+
+	/// Read a raw status response from the bus.
+	pub async fn read_status_response(&mut self) -> Result<AsyncResponse<Stream>, ReadError<std::io::Error>> {
+		let response = self.read_raw_response().await?;
+		crate::InvalidInstruction::check(response.instruction_id(), instructions::instruction_id::STATUS)?;
+		crate::MotorError::check(response.error())?;
+		Ok(response)
+	}
+
+	/// Try to parse a complete status message out of what's already buffered, without reading any
+	/// more data from the stream.
+	///
+	/// Mirrors `Bus`'s helper of the same name, since `AsyncBus` follows the same framing rules.
+	/// Returns `Ok(Some(len))` if a full (stuffed) message of `len` bytes is ready to be read out of
+	/// the read buffer. Returns `Ok(None)` if more data is needed before a message can be parsed,
+	/// growing the read buffer first if that's both necessary and possible.
+	fn try_parse_buffered_message(&mut self) -> Result<Option<usize>, ReadError<std::io::Error>> {
+		self.remove_garbage();
+
+		let read_buffer = &self.read_buffer[..self.read_len];
+		if !read_buffer.starts_with(&HEADER_PREFIX) {
+			return Ok(None);
+		}
+
+		let message_len = match framing::status_message_len(read_buffer) {
+			Ok(Some(message_len)) => message_len,
+			// The header itself doesn't fit in the buffer yet: grow just enough to read
+			// it, or give up if we're already at the configured ceiling, rather than
+			// calling read() into an empty slice until the timeout expires.
+			Ok(None) if self.read_len >= self.read_buffer.len() => {
+				if !self.read_buffer.try_grow(STATUS_HEADER_SIZE, self.max_read_buffer_size) {
+					// We can't even hold the status header: drop everything buffered so
+					// far instead of leaving it there to be re-discovered (and re-rejected) by
+					// the very next call, which would otherwise busy-spin on an empty read().
+					self.consume_read_bytes(self.read_len);
+					return Err(ReadError::MessageTooLarge { declared_len: STATUS_HEADER_SIZE });
+				}
+				return Ok(None);
+			},
+			Ok(None) => return Ok(None),
+			Err(e) => {
+				self.consume_read_bytes(STATUS_HEADER_SIZE);
+				return Err(e.into());
+			},
+		};
+
+		if message_len > self.max_read_buffer_size {
+			self.consume_read_bytes(self.read_len);
+			return Err(ReadError::MessageTooLarge { declared_len: message_len });
+		}
+
+		if self.read_len >= message_len {
+			return Ok(Some(message_len));
+		}
+
+		if self.read_buffer.len() < message_len && !self.read_buffer.try_grow(message_len, self.max_read_buffer_size) {
+			self.consume_read_bytes(self.read_len);
+			return Err(ReadError::MessageTooLarge { declared_len: message_len });
+		}
+
+		Ok(None)
+	}
+
+	/// Read a single raw status response from the bus, without checking the instruction ID or motor error field.
+	async fn read_raw_response(&mut self) -> Result<AsyncResponse<Stream>, ReadError<std::io::Error>> {
+		let read_timeout = self.read_timeout;
+		let stuffed_message_len = tokio::time::timeout(read_timeout, async {
+			loop {
+				// Always try to parse a message out of what's already buffered before waiting on
+				// more I/O: a single read() can return more than one reply (e.g. several Sync Read
+				// replies arriving back to back), and the rest must not sit there unparsed until
+				// another read() happens to bring in fresh bytes.
+				if let Some(message_len) = self.try_parse_buffered_message()? {
+					break Ok::<usize, ReadError<std::io::Error>>(message_len);
+				}
+
+				// Try to read more data into the buffer.
+				let new_data = self.stream.read(&mut self.read_buffer[self.read_len..]).await.map_err(ReadError::Io)?;
+				if new_data == 0 {
+					// Unlike `Bus`'s `ProtoRead::read`, `tokio::io::AsyncRead::read` only ever
+					// returns `Ok(0)` at end-of-stream, and resolves immediately rather than
+					// yielding: looping on it here would spin forever without giving the
+					// `tokio::time::timeout` around this block a chance to actually apply.
+					break Err(ReadError::Io(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "end of stream reached while reading a status response")));
+				}
+
+				self.read_len += new_data;
+			}
+		})
+		.await
+		.map_err(|_| ReadError::Timeout)??;
+
+		let parameters_end = stuffed_message_len - 2;
+		trace!("read packet: {:02X?}", &self.read_buffer[..parameters_end]);
+
+		if let Err(e) = framing::verify_checksum(&self.read_buffer, parameters_end) {
+			self.consume_read_bytes(stuffed_message_len);
+			return Err(e.into());
+		}
+
+		// Remove byte-stuffing from the parameters.
+		let parameter_count = bytestuff::unstuff_inplace(&mut self.read_buffer[STATUS_HEADER_SIZE..parameters_end]);
+
+		// Creating the response struct here means that the data gets purged from the buffer even if we return early using the try operator.
+		let response = AsyncResponse {
+			bus: self,
+			stuffed_message_len,
+			parameter_count,
+		};
+
+		Ok(response)
+	}
+}
+
+impl<Stream> AsyncBus<Stream> {
+	/// Remove leading garbage data from the read buffer.
+	fn remove_garbage(&mut self) {
+		let garbage_len = framing::find_header(&self.read_buffer[..self.read_len]);
+		#[cfg(feature = "log")]
+		if garbage_len > 0 {
+			log::debug!("Skipping {} bytes of leading garbage.", garbage_len);
+			log::trace!("Skipped garbage: {:02X?}", &self.read_buffer[..garbage_len]);
+		}
+		self.consume_read_bytes(garbage_len);
+	}
+
+	fn consume_read_bytes(&mut self, len: usize) {
+		debug_assert!(len <= self.read_len);
+		self.read_buffer.copy_within(len..self.read_len, 0);
+		self.read_len -= len;
+	}
+}
+
+/// A status response that is currently in the read buffer of an [`AsyncBus`].
+///
+/// When dropped, the response data is removed from the read buffer.
+pub struct AsyncResponse<'a, Stream> {
+	/// The bus that read the message.
+	bus: &'a mut AsyncBus<Stream>,
+
+	/// The total length of the stuffed message.
+	stuffed_message_len: usize,
+
+	/// The number of parameters after removing byte-stuffing.
+	parameter_count: usize,
+}
+
+impl<'a, Stream> AsyncResponse<'a, Stream> {
+	/// Get the raw bytes of the message.
+	///
+	/// This includes the message header and the parameters.
+	/// It does not include the CRC or byte-stuffing.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.bus.read_buffer[..STATUS_HEADER_SIZE + self.parameter_count]
+	}
+
+	/// The packet ID of the response.
+	pub fn packet_id(&self) -> u8 {
+		self.as_bytes()[4]
+	}
+
+	/// The instruction ID of the response.
+	pub fn instruction_id(&self) -> u8 {
+		self.as_bytes()[7]
+	}
+
+	/// The error field of the response.
+	pub fn error(&self) -> u8 {
+		self.as_bytes()[8]
+	}
+
+	/// The parameters of the response.
+	pub fn parameters(&self) -> &[u8] {
+		&self.as_bytes()[STATUS_HEADER_SIZE..][..self.parameter_count]
+	}
+}
+
+impl<'a, Stream> StatusResponse for AsyncResponse<'a, Stream> {
+	fn packet_id(&self) -> u8 {
+		AsyncResponse::packet_id(self)
+	}
+
+	fn instruction_id(&self) -> u8 {
+		AsyncResponse::instruction_id(self)
+	}
+
+	fn error(&self) -> u8 {
+		AsyncResponse::error(self)
+	}
+
+	fn parameters(&self) -> &[u8] {
+		AsyncResponse::parameters(self)
+	}
+}
+
+impl<'a, Stream> Drop for AsyncResponse<'a, Stream> {
+	fn drop(&mut self) {
+		self.bus.consume_read_bytes(self.stuffed_message_len);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use core::pin::Pin;
+	use core::task::{Context, Poll};
+
+	use assert2::assert;
+	use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+	use super::*;
+
+	/// A fixed, pre-recorded byte stream for feeding canned replies to an [`AsyncBus`] in tests.
+	///
+	/// Mirrors how `bus.rs`'s tests use [`std::io::Cursor`], but also implements `AsyncWrite` as a
+	/// no-op sink, since `AsyncBus` requires its stream to implement both traits.
+	struct TestStream {
+		data: Vec<u8>,
+		position: usize,
+	}
+
+	impl TestStream {
+		fn new(data: Vec<u8>) -> Self {
+			Self { data, position: 0 }
+		}
+	}
+
+	impl AsyncRead for TestStream {
+		fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+			let this = self.get_mut();
+			let available = &this.data[this.position..];
+			let len = available.len().min(buf.remaining());
+			buf.put_slice(&available[..len]);
+			this.position += len;
+			Poll::Ready(Ok(()))
+		}
+	}
+
+	impl AsyncWrite for TestStream {
+		fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+			Poll::Ready(Ok(buf.len()))
+		}
+
+		fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+			Poll::Ready(Ok(()))
+		}
+
+		fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+			Poll::Ready(Ok(()))
+		}
+	}
+
+	/// Build the raw bytes of an (unstuffed) status response, as a motor would send it.
+	///
+	/// Mirrors the identically-named helper in `bus.rs`'s tests.
+	fn encode_status_response(packet_id: u8, error: u8, parameters: &[u8]) -> Vec<u8> {
+		let declared_len = parameters.len() as u16 + 4; // instruction + error + parameters + checksum
+		let mut message = vec![0xFF, 0xFF, 0xFD, 0x00, packet_id, 0, 0, instructions::instruction_id::STATUS, error];
+		write_u16_le(&mut message[5..], declared_len);
+		message.extend_from_slice(parameters);
+		let checksum = calculate_checksum(0, &message);
+		message.extend_from_slice(&checksum.to_le_bytes());
+		message
+	}
+
+	#[tokio::test]
+	async fn test_read_status_response_roundtrip() {
+		let data = encode_status_response(1, 0, &[1, 2, 3]);
+		let mut bus = AsyncBus::with_buffer_sizes(TestStream::new(data), Duration::from_millis(50), 64, 64);
+		let response = bus.read_status_response().await.unwrap();
+		assert!(response.packet_id() == 1);
+		assert!(response.parameters() == [1, 2, 3]);
+	}
+
+	#[tokio::test]
+	async fn test_read_status_response_message_too_large() {
+		// A status header declaring a body far larger than the configured read buffer ceiling.
+		let header = vec![0xFF, 0xFF, 0xFD, 0x00, 1, 0xFF, 0xFF, 0x55, 0];
+		let mut bus = AsyncBus::with_buffer_sizes(TestStream::new(header), Duration::from_millis(50), 16, 16);
+		let result = bus.read_status_response().await;
+		assert!(let Err(ReadError::MessageTooLarge { .. }) = result);
+	}
+
+	#[tokio::test]
+	async fn test_read_status_response_message_too_large_does_not_leave_bytes_buffered() {
+		// Same oversized header as above, but read twice on the same bus without an intervening
+		// write_instruction (the only other thing that resets read_len): the first call must drop
+		// the rejected header from the read buffer, so the second call finds nothing buffered, hits
+		// the end of the stream and reports UnexpectedEof instead of the stale header again.
+		let header = vec![0xFF, 0xFF, 0xFD, 0x00, 1, 0xFF, 0xFF, 0x55, 0];
+		let mut bus = AsyncBus::with_buffer_sizes(TestStream::new(header), Duration::from_millis(50), 16, 16);
+		assert!(let Err(ReadError::MessageTooLarge { .. }) = bus.read_status_response().await);
+		assert!(let Err(ReadError::Io(_)) = bus.read_status_response().await);
+	}
+
+	#[tokio::test]
+	async fn test_read_status_response_multiple_replies_in_one_read() {
+		// Two replies delivered by a single poll_read call: both must get parsed, not just the first.
+		let mut data = Vec::new();
+		data.extend(encode_status_response(1, 0, &[10]));
+		data.extend(encode_status_response(2, 0, &[20]));
+
+		let mut bus = AsyncBus::with_buffer_sizes(TestStream::new(data), Duration::from_millis(50), 64, 64);
+		let first = bus.read_status_response().await.unwrap();
+		assert!(first.packet_id() == 1);
+		assert!(first.parameters() == [10]);
+		drop(first);
+
+		let second = bus.read_status_response().await.unwrap();
+		assert!(second.packet_id() == 2);
+		assert!(second.parameters() == [20]);
+	}
+
+	#[tokio::test]
+	async fn test_read_status_response_eof() {
+		let mut bus = AsyncBus::with_buffer_sizes(TestStream::new(Vec::new()), Duration::from_millis(50), 64, 64);
+		let result = bus.read_status_response().await;
+		assert!(let Err(ReadError::Io(_)) = result);
+	}
+}
@@ -0,0 +1,101 @@
+//! Byte-stuffing for Dynamixel Protocol 2.0 message bodies.
+//!
+//! The message body is stuffed so that the header prefix `0xFF 0xFF 0xFD` can never occur
+//! halfway through a message: whenever that sequence appears, an extra `0xFD` is inserted
+//! right after it, and removed again on the receiving end.
+
+/// Stuff `buffer[..len]` in place, returning the length of the stuffed data.
+///
+/// `buffer` must have enough spare room after `len` to hold the inserted bytes.
+pub(crate) fn stuff_inplace(buffer: &mut [u8], len: usize) -> Result<usize, ()> {
+	let mut extra = 0;
+	let mut i = 0;
+	while i + 2 < len {
+		if buffer[i] == 0xFF && buffer[i + 1] == 0xFF && buffer[i + 2] == 0xFD {
+			extra += 1;
+			i += 3;
+		} else {
+			i += 1;
+		}
+	}
+
+	if extra == 0 {
+		return Ok(len);
+	}
+
+	let stuffed_len = len + extra;
+	if stuffed_len > buffer.len() {
+		return Err(());
+	}
+
+	// Walk backwards so we can shift bytes into their stuffed position in place.
+	// The source bytes at `src` are never overwritten before they are read,
+	// because `dst` always stays ahead of `src`.
+	let mut src = len;
+	let mut dst = stuffed_len;
+	while src > 0 {
+		src -= 1;
+		let byte = buffer[src];
+		if byte == 0xFD && src >= 2 && buffer[src - 1] == 0xFF && buffer[src - 2] == 0xFF {
+			dst -= 1;
+			buffer[dst] = 0xFD;
+		}
+		dst -= 1;
+		buffer[dst] = byte;
+	}
+
+	Ok(stuffed_len)
+}
+
+/// Remove byte-stuffing from `buffer` in place, returning the length of the unstuffed data.
+pub(crate) fn unstuff_inplace(buffer: &mut [u8]) -> usize {
+	let len = buffer.len();
+	let mut read = 0;
+	let mut write = 0;
+	while read < len {
+		let byte = buffer[read];
+		buffer[write] = byte;
+		write += 1;
+		read += 1;
+
+		if byte == 0xFD && write >= 3 && buffer[write - 2] == 0xFF && buffer[write - 3] == 0xFF {
+			// This was a real header-like sequence, so the next byte (if any) is a stuffed 0xFD to drop.
+			if read < len && buffer[read] == 0xFD {
+				read += 1;
+			}
+		}
+	}
+	write
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn test_stuff_unstuff_roundtrip() {
+		let mut buffer = [0xFF, 0xFF, 0xFD, 0x01, 0x02, 0, 0];
+		let stuffed_len = stuff_inplace(&mut buffer, 5).unwrap();
+		assert!(stuffed_len == 6);
+		assert!(buffer[..stuffed_len] == [0xFF, 0xFF, 0xFD, 0xFD, 0x01, 0x02]);
+
+		let unstuffed_len = unstuff_inplace(&mut buffer[..stuffed_len]);
+		assert!(unstuffed_len == 5);
+		assert!(buffer[..unstuffed_len] == [0xFF, 0xFF, 0xFD, 0x01, 0x02]);
+	}
+
+	#[test]
+	fn test_stuff_no_match() {
+		let mut buffer = [1, 2, 3, 0, 0];
+		let stuffed_len = stuff_inplace(&mut buffer, 3).unwrap();
+		assert!(stuffed_len == 3);
+		assert!(buffer[..stuffed_len] == [1, 2, 3]);
+	}
+
+	#[test]
+	fn test_stuff_buffer_too_small() {
+		let mut buffer = [0xFF, 0xFF, 0xFD];
+		assert!(let Err(()) = stuff_inplace(&mut buffer, 3));
+	}
+}
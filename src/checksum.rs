@@ -0,0 +1,20 @@
+//! CRC-16 checksum used to validate Dynamixel Protocol 2.0 messages.
+
+/// Update a running CRC-16 checksum with more data.
+///
+/// Dynamixel Protocol 2.0 uses the reflected CRC-16 (poly 0x8005, no final XOR)
+/// over the whole message, starting from a checksum of `0`.
+pub(crate) fn calculate_checksum(initial: u16, data: &[u8]) -> u16 {
+	let mut crc = initial;
+	for &byte in data {
+		crc ^= byte as u16;
+		for _ in 0..8 {
+			if crc & 1 != 0 {
+				crc = (crc >> 1) ^ 0xA001;
+			} else {
+				crc >>= 1;
+			}
+		}
+	}
+	crc
+}
@@ -0,0 +1,43 @@
+//! An injectable clock, so read timeouts don't require `std::time`.
+
+use core::time::Duration;
+
+/// A monotonic clock used by [`Bus`](crate::Bus) to implement read timeouts.
+///
+/// Implement this on bare-metal targets where `std::time::Instant` isn't available, for example by
+/// wrapping a hardware timer peripheral.
+pub trait Clock {
+	/// An opaque point in time, as produced by [`Self::now`].
+	type Instant: Copy;
+
+	/// The current time.
+	fn now(&self) -> Self::Instant;
+
+	/// Compute the point in time `duration` after `instant`.
+	fn deadline(&self, instant: Self::Instant, duration: Duration) -> Self::Instant;
+
+	/// True if `instant` lies at or before the current time.
+	fn is_elapsed(&self, instant: Self::Instant) -> bool;
+}
+
+/// A [`Clock`] backed by [`std::time::Instant`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdClock;
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+	type Instant = std::time::Instant;
+
+	fn now(&self) -> Self::Instant {
+		std::time::Instant::now()
+	}
+
+	fn deadline(&self, instant: Self::Instant, duration: Duration) -> Self::Instant {
+		instant + duration
+	}
+
+	fn is_elapsed(&self, instant: Self::Instant) -> bool {
+		std::time::Instant::now() >= instant
+	}
+}
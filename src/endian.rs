@@ -0,0 +1,21 @@
+//! Helpers for reading and writing little-endian integers used in Dynamixel Protocol 2.0 messages.
+
+/// Read a little-endian `u16` from the start of `buffer`.
+pub(crate) fn read_u16_le(buffer: &[u8]) -> u16 {
+	u16::from_le_bytes([buffer[0], buffer[1]])
+}
+
+/// Read a little-endian `u32` from the start of `buffer`.
+pub(crate) fn read_u32_le(buffer: &[u8]) -> u32 {
+	u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]])
+}
+
+/// Write a `u16` to the start of `buffer` as little-endian bytes.
+pub(crate) fn write_u16_le(buffer: &mut [u8], value: u16) {
+	buffer[..2].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Write a `u32` to the start of `buffer` as little-endian bytes.
+pub(crate) fn write_u32_le(buffer: &mut [u8], value: u32) {
+	buffer[..4].copy_from_slice(&value.to_le_bytes());
+}
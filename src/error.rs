@@ -0,0 +1,301 @@
+//! Error types returned by [`crate::Bus`].
+
+use core::fmt;
+
+/// The checksum of a received message did not match the computed checksum.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidChecksum {
+	/// The checksum that was encoded in the message.
+	pub message: u16,
+
+	/// The checksum computed from the received bytes.
+	pub computed: u16,
+}
+
+impl fmt::Display for InvalidChecksum {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid checksum: message claims {:#06X}, computed {:#06X}", self.message, self.computed)
+	}
+}
+
+/// The packet ID of a status response did not match the packet ID of the instruction that was sent.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidPacketId {
+	/// The packet ID of the response.
+	pub actual: u8,
+
+	/// The packet ID of the instruction that was sent.
+	pub expected: u8,
+}
+
+impl InvalidPacketId {
+	pub(crate) fn check(actual: u8, expected: u8) -> Result<(), Self> {
+		if actual == expected {
+			Ok(())
+		} else {
+			Err(Self { actual, expected })
+		}
+	}
+}
+
+impl fmt::Display for InvalidPacketId {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid packet ID: expected {}, got {}", self.expected, self.actual)
+	}
+}
+
+/// The instruction ID of a received message was not the expected value.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidInstruction {
+	/// The instruction ID of the response.
+	pub actual: u8,
+
+	/// The instruction ID that was expected.
+	pub expected: u8,
+}
+
+impl InvalidInstruction {
+	pub(crate) fn check(actual: u8, expected: u8) -> Result<(), Self> {
+		if actual == expected {
+			Ok(())
+		} else {
+			Err(Self { actual, expected })
+		}
+	}
+}
+
+impl fmt::Display for InvalidInstruction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid instruction ID: expected {:#04X}, got {:#04X}", self.expected, self.actual)
+	}
+}
+
+/// A status response declared a body length too short to even cover its own instruction and error fields.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidMessageLength {
+	/// The length field declared by the message header.
+	pub declared_len: usize,
+}
+
+impl fmt::Display for InvalidMessageLength {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid message length: header declares a length of {}, which is too short to cover the instruction and error fields", self.declared_len)
+	}
+}
+
+/// A status response had fewer parameter bytes than the instruction needed to parse it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidParameterCount {
+	/// The number of parameter bytes actually present in the response.
+	pub actual: usize,
+
+	/// The minimum number of parameter bytes required.
+	pub expected: usize,
+}
+
+impl InvalidParameterCount {
+	pub(crate) fn check(actual: usize, expected: usize) -> Result<(), Self> {
+		if actual >= expected {
+			Ok(())
+		} else {
+			Err(Self { actual, expected })
+		}
+	}
+}
+
+impl fmt::Display for InvalidParameterCount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid parameter count: expected at least {}, got {}", self.expected, self.actual)
+	}
+}
+
+/// A motor reported an error in the `error` field of its status response.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MotorError {
+	/// The raw error field of the status response.
+	pub raw: u8,
+}
+
+impl MotorError {
+	pub(crate) fn check(raw: u8) -> Result<(), Self> {
+		if raw & 0x7F == 0 {
+			Ok(())
+		} else {
+			Err(Self { raw })
+		}
+	}
+
+	/// True if the motor raised the hardware error alert bit.
+	pub fn alert(&self) -> bool {
+		self.raw & 0x80 != 0
+	}
+}
+
+impl fmt::Display for MotorError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "motor reported error {:#04X}", self.raw)
+	}
+}
+
+/// An error that can occur while writing an instruction to the bus.
+///
+/// Generic over `E`, the error type of the underlying [`Transport`](crate::transport::Transport).
+#[derive(Debug)]
+pub enum WriteError<E> {
+	/// The write buffer is not large enough to hold the outgoing message.
+	BufferTooSmall {
+		/// The number of bytes the message needs.
+		required: usize,
+		/// The number of bytes actually available in the write buffer.
+		available: usize,
+	},
+
+	/// Byte-stuffing the message body failed because the write buffer has no room for the stuffing bytes.
+	StuffingFailed,
+
+	/// The transport failed to write the message.
+	Io(E),
+}
+
+impl<E: fmt::Display> fmt::Display for WriteError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::BufferTooSmall { required, available } => {
+				write!(f, "write buffer too small: message needs {required} bytes, buffer has room for {available}")
+			},
+			Self::StuffingFailed => write!(f, "write buffer has no room for byte-stuffing the message body"),
+			Self::Io(e) => write!(f, "failed to write to the transport: {e}"),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for WriteError<E> {}
+
+/// An error that can occur while reading a status response from the bus.
+///
+/// Generic over `E`, the error type of the underlying [`Transport`](crate::transport::Transport).
+#[derive(Debug)]
+pub enum ReadError<E> {
+	/// The transport failed to read a message.
+	Io(E),
+
+	/// No valid status response arrived before the configured read timeout elapsed.
+	Timeout,
+
+	/// The message needs more room than the read buffer is allowed to grow to.
+	MessageTooLarge {
+		/// The number of bytes the read buffer would need: the total message length computed from
+		/// the header's length field, or the status header size if not even the header fits.
+		declared_len: usize,
+	},
+
+	/// The checksum of the response did not match.
+	InvalidChecksum(InvalidChecksum),
+
+	/// The message header declared a body length too short to be valid.
+	InvalidMessageLength(InvalidMessageLength),
+
+	/// The response had fewer parameter bytes than the instruction needed to parse it.
+	InvalidParameterCount(InvalidParameterCount),
+
+	/// The packet ID of the response did not match the instruction that was sent.
+	InvalidPacketId(InvalidPacketId),
+
+	/// The instruction ID of the response was not a status response.
+	InvalidInstruction(InvalidInstruction),
+
+	/// The motor reported an error.
+	MotorError(MotorError),
+}
+
+impl<E: fmt::Display> fmt::Display for ReadError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "failed to read from the transport: {e}"),
+			Self::Timeout => write!(f, "timed out waiting for a status response"),
+			Self::MessageTooLarge { declared_len } => write!(f, "message too large: {declared_len} bytes exceeds the maximum read buffer size"),
+			Self::InvalidChecksum(e) => e.fmt(f),
+			Self::InvalidMessageLength(e) => e.fmt(f),
+			Self::InvalidParameterCount(e) => e.fmt(f),
+			Self::InvalidPacketId(e) => e.fmt(f),
+			Self::InvalidInstruction(e) => e.fmt(f),
+			Self::MotorError(e) => e.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ReadError<E> {}
+
+impl<E> From<InvalidChecksum> for ReadError<E> {
+	fn from(value: InvalidChecksum) -> Self {
+		Self::InvalidChecksum(value)
+	}
+}
+
+impl<E> From<InvalidMessageLength> for ReadError<E> {
+	fn from(value: InvalidMessageLength) -> Self {
+		Self::InvalidMessageLength(value)
+	}
+}
+
+impl<E> From<InvalidParameterCount> for ReadError<E> {
+	fn from(value: InvalidParameterCount) -> Self {
+		Self::InvalidParameterCount(value)
+	}
+}
+
+impl<E> From<InvalidPacketId> for ReadError<E> {
+	fn from(value: InvalidPacketId) -> Self {
+		Self::InvalidPacketId(value)
+	}
+}
+
+impl<E> From<InvalidInstruction> for ReadError<E> {
+	fn from(value: InvalidInstruction) -> Self {
+		Self::InvalidInstruction(value)
+	}
+}
+
+impl<E> From<MotorError> for ReadError<E> {
+	fn from(value: MotorError) -> Self {
+		Self::MotorError(value)
+	}
+}
+
+/// An error that can occur while performing a single-response transfer.
+///
+/// Generic over `E`, the error type of the underlying [`Transport`](crate::transport::Transport).
+#[derive(Debug)]
+pub enum TransferError<E> {
+	/// Writing the instruction failed.
+	Write(WriteError<E>),
+
+	/// Reading the status response failed.
+	Read(ReadError<E>),
+}
+
+impl<E: fmt::Display> fmt::Display for TransferError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Write(e) => e.fmt(f),
+			Self::Read(e) => e.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for TransferError<E> {}
+
+impl<E> From<WriteError<E>> for TransferError<E> {
+	fn from(value: WriteError<E>) -> Self {
+		Self::Write(value)
+	}
+}
+
+impl<E> From<ReadError<E>> for TransferError<E> {
+	fn from(value: ReadError<E>) -> Self {
+		Self::Read(value)
+	}
+}
@@ -0,0 +1,103 @@
+//! Pure, I/O-free parts of the Dynamixel Protocol 2.0 status response state machine.
+//!
+//! Shared between the blocking [`Bus`](crate::Bus) and the async bus, so the framing logic can't
+//! drift between the two: only the part that actually waits on I/O differs.
+
+use crate::checksum::calculate_checksum;
+use crate::endian::read_u16_le;
+use crate::{InvalidChecksum, InvalidMessageLength};
+
+/// The fixed four-byte prefix that starts every Dynamixel Protocol 2.0 message.
+pub(crate) const HEADER_PREFIX: [u8; 4] = [0xFF, 0xFF, 0xFD, 0x00];
+
+/// The size of an instruction message header, before the parameters.
+pub(crate) const HEADER_SIZE: usize = 8;
+
+/// The size of a status response header, before the parameters.
+pub(crate) const STATUS_HEADER_SIZE: usize = 9;
+
+/// Find the potential starting position of a header.
+///
+/// This will return the first possible position of the header prefix.
+/// Note that if the buffer ends with a partial header prefix,
+/// the start position of the partial header prefix is returned.
+pub(crate) fn find_header(buffer: &[u8]) -> usize {
+	for i in 0..buffer.len() {
+		let possible_prefix = HEADER_PREFIX.len().min(buffer.len() - i);
+		if buffer[i..].starts_with(&HEADER_PREFIX[..possible_prefix]) {
+			return i;
+		}
+	}
+
+	buffer.len()
+}
+
+/// Compute the total stuffed length of a status message, given its (possibly still incomplete) header.
+///
+/// Returns `None` if `buffer` doesn't contain a full status header yet.
+/// Returns `Err` if the header declares a length too short to cover its own instruction and error fields.
+pub(crate) fn status_message_len(buffer: &[u8]) -> Result<Option<usize>, InvalidMessageLength> {
+	if buffer.len() < STATUS_HEADER_SIZE {
+		return Ok(None);
+	}
+
+	let declared_len = buffer[5] as usize + buffer[6] as usize * 256;
+	// Length includes instruction and error fields, which is already included in STATUS_HEADER_SIZE too.
+	let body_len = declared_len.checked_sub(2).ok_or(InvalidMessageLength { declared_len })?;
+	Ok(Some(STATUS_HEADER_SIZE + body_len))
+}
+
+/// Verify the checksum of a received message.
+///
+/// `buffer[..parameters_end]` is the part of the message covered by the checksum,
+/// and `buffer[parameters_end..]` holds the little-endian checksum itself.
+pub(crate) fn verify_checksum(buffer: &[u8], parameters_end: usize) -> Result<(), InvalidChecksum> {
+	let checksum_message = read_u16_le(&buffer[parameters_end..]);
+	let checksum_computed = calculate_checksum(0, &buffer[..parameters_end]);
+	if checksum_message == checksum_computed {
+		Ok(())
+	} else {
+		Err(InvalidChecksum {
+			message: checksum_message,
+			computed: checksum_computed,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn test_find_garbage_end() {
+		assert!(find_header(&[0xFF]) == 0);
+		assert!(find_header(&[0xFF, 0xFF]) == 0);
+		assert!(find_header(&[0xFF, 0xFF, 0xFD]) == 0);
+		assert!(find_header(&[0xFF, 0xFF, 0xFD, 0x00]) == 0);
+		assert!(find_header(&[0xFF, 0xFF, 0xFD, 0x00, 9]) == 0);
+
+		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF]) == 5);
+		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF]) == 5);
+		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF, 0xFD]) == 5);
+		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF, 0xFD, 0x00]) == 5);
+		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 0xFF, 0xFD, 0x00, 9]) == 5);
+
+		assert!(find_header(&[0xFF, 1]) == 2);
+		assert!(find_header(&[0, 1, 2, 3, 4, 0xFF, 6]) == 7);
+	}
+
+	#[test]
+	fn test_status_message_len() {
+		// A full, sane header: length field of 4 means 2 bytes of parameters.
+		assert!(status_message_len(&[0xFF, 0xFF, 0xFD, 0x00, 1, 4, 0, 0x55, 0]) == Ok(Some(STATUS_HEADER_SIZE + 2)));
+
+		// An incomplete header doesn't have enough bytes to know the length yet.
+		assert!(status_message_len(&[0xFF, 0xFF, 0xFD, 0x00, 1, 4, 0, 0x55]) == Ok(None));
+
+		// A declared length of 0 or 1 can't even cover the instruction and error fields that
+		// STATUS_HEADER_SIZE already accounts for: this must not underflow.
+		assert!(status_message_len(&[0xFF, 0xFF, 0xFD, 0x00, 1, 0, 0, 0x55, 0]) == Err(InvalidMessageLength { declared_len: 0 }));
+		assert!(status_message_len(&[0xFF, 0xFF, 0xFD, 0x00, 1, 1, 0, 0x55, 0]) == Err(InvalidMessageLength { declared_len: 1 }));
+	}
+}
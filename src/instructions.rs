@@ -0,0 +1,592 @@
+//! Typed Dynamixel Protocol 2.0 instructions.
+//!
+//! These build on top of [`Bus::transfer_single`](crate::Bus::transfer_single) and
+//! [`Bus::write_instruction`](crate::Bus::write_instruction) to give each instruction its own
+//! type with named parameters, instead of requiring callers to hand-encode bytes and
+//! interpret [`Response::parameters`](crate::Response::parameters) themselves.
+
+use crate::endian::{read_u16_le, read_u32_le, write_u16_le, write_u32_le};
+use crate::{InvalidParameterCount, ReadError};
+
+/// Raw instruction and status IDs used by Dynamixel Protocol 2.0.
+pub mod instruction_id {
+	/// Instruction ID for a ping instruction.
+	pub const PING: u8 = 0x01;
+	/// Instruction ID for a read instruction.
+	pub const READ: u8 = 0x02;
+	/// Instruction ID for a write instruction.
+	pub const WRITE: u8 = 0x03;
+	/// Instruction ID for a reg-write instruction.
+	pub const REG_WRITE: u8 = 0x04;
+	/// Instruction ID for an action instruction.
+	pub const ACTION: u8 = 0x05;
+	/// Instruction ID for a factory-reset instruction.
+	pub const FACTORY_RESET: u8 = 0x06;
+	/// Instruction ID for a reboot instruction.
+	pub const REBOOT: u8 = 0x08;
+	/// Instruction ID for a clear instruction.
+	pub const CLEAR: u8 = 0x10;
+	/// Instruction ID of a status response.
+	pub const STATUS: u8 = 0x55;
+	/// Instruction ID for a sync-read instruction.
+	pub const SYNC_READ: u8 = 0x82;
+	/// Instruction ID for a sync-write instruction.
+	pub const SYNC_WRITE: u8 = 0x83;
+	/// Instruction ID for a bulk-read instruction.
+	pub const BULK_READ: u8 = 0x92;
+	/// Instruction ID for a bulk-write instruction.
+	pub const BULK_WRITE: u8 = 0x93;
+}
+
+/// A received status response, abstracted over the bus that read it.
+///
+/// Implemented by [`Response`](crate::Response) and by the async bus's response type, so a single
+/// [`Instruction::parse_response`] implementation works for both.
+pub trait StatusResponse {
+	/// The packet ID of the response.
+	fn packet_id(&self) -> u8;
+
+	/// The instruction ID of the response.
+	fn instruction_id(&self) -> u8;
+
+	/// The error field of the response.
+	fn error(&self) -> u8;
+
+	/// The parameters of the response.
+	fn parameters(&self) -> &[u8];
+}
+
+/// A typed Dynamixel Protocol 2.0 instruction.
+///
+/// Implementing this trait lets [`Bus::execute`](crate::Bus::execute) encode the instruction
+/// parameters and decode the status response, so callers don't have to juggle byte offsets
+/// themselves.
+pub trait Instruction {
+	/// The value produced by parsing a successful status response to this instruction.
+	type Response;
+
+	/// The packet ID of the motor this instruction targets.
+	fn packet_id(&self) -> u8;
+
+	/// The raw instruction ID to put in the request header.
+	fn instruction_id(&self) -> u8;
+
+	/// The number of parameter bytes [`Self::encode_parameters`] will write.
+	fn request_parameters_len(&self) -> usize;
+
+	/// Encode the instruction parameters into `buffer`.
+	///
+	/// `buffer` is exactly [`Self::request_parameters_len`] bytes long.
+	fn encode_parameters(&self, buffer: &mut [u8]);
+
+	/// Parse a status response into this instruction's response type.
+	fn parse_response<R: StatusResponse, E>(&self, response: &R) -> Result<Self::Response, ReadError<E>>;
+}
+
+/// A value that can be read from or written to a motor's control table.
+pub trait Value: Sized {
+	/// The number of bytes this value occupies in the control table.
+	const ENCODED_LEN: usize;
+
+	/// Decode a value from its little-endian byte representation.
+	fn decode(buffer: &[u8]) -> Self;
+
+	/// Encode this value as little-endian bytes.
+	fn encode(&self, buffer: &mut [u8]);
+}
+
+impl Value for u8 {
+	const ENCODED_LEN: usize = 1;
+
+	fn decode(buffer: &[u8]) -> Self {
+		buffer[0]
+	}
+
+	fn encode(&self, buffer: &mut [u8]) {
+		buffer[0] = *self;
+	}
+}
+
+impl Value for u16 {
+	const ENCODED_LEN: usize = 2;
+
+	fn decode(buffer: &[u8]) -> Self {
+		read_u16_le(buffer)
+	}
+
+	fn encode(&self, buffer: &mut [u8]) {
+		write_u16_le(buffer, *self);
+	}
+}
+
+impl Value for u32 {
+	const ENCODED_LEN: usize = 4;
+
+	fn decode(buffer: &[u8]) -> Self {
+		read_u32_le(buffer)
+	}
+
+	fn encode(&self, buffer: &mut [u8]) {
+		write_u32_le(buffer, *self);
+	}
+}
+
+/// Ping a single motor, requesting its model number and firmware version.
+pub struct Ping {
+	packet_id: u8,
+}
+
+impl Ping {
+	/// Create a new ping instruction for the given motor ID.
+	pub fn new(packet_id: u8) -> Self {
+		Self { packet_id }
+	}
+}
+
+/// The response to a [`Ping`] instruction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PingResponse {
+	/// The model number of the motor.
+	pub model_number: u16,
+
+	/// The firmware version of the motor.
+	pub firmware_version: u8,
+}
+
+impl Instruction for Ping {
+	type Response = PingResponse;
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::PING
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		0
+	}
+
+	fn encode_parameters(&self, _buffer: &mut [u8]) {}
+
+	fn parse_response<R: StatusResponse, E>(&self, response: &R) -> Result<Self::Response, ReadError<E>> {
+		let parameters = response.parameters();
+		InvalidParameterCount::check(parameters.len(), 3)?;
+		Ok(PingResponse {
+			model_number: read_u16_le(parameters),
+			firmware_version: parameters[2],
+		})
+	}
+}
+
+/// Read a value from a motor's control table.
+pub struct Read<T> {
+	packet_id: u8,
+	address: u16,
+	marker: core::marker::PhantomData<T>,
+}
+
+impl<T: Value> Read<T> {
+	/// Create a new read instruction for the given motor ID and control table address.
+	pub fn new(packet_id: u8, address: u16) -> Self {
+		Self {
+			packet_id,
+			address,
+			marker: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<T: Value> Instruction for Read<T> {
+	type Response = T;
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::READ
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		4
+	}
+
+	fn encode_parameters(&self, buffer: &mut [u8]) {
+		write_u16_le(buffer, self.address);
+		write_u16_le(&mut buffer[2..], T::ENCODED_LEN as u16);
+	}
+
+	fn parse_response<R: StatusResponse, E>(&self, response: &R) -> Result<Self::Response, ReadError<E>> {
+		let parameters = response.parameters();
+		InvalidParameterCount::check(parameters.len(), T::ENCODED_LEN)?;
+		Ok(T::decode(parameters))
+	}
+}
+
+/// Write a value to a motor's control table.
+pub struct Write<T> {
+	packet_id: u8,
+	address: u16,
+	value: T,
+}
+
+impl<T: Value> Write<T> {
+	/// Create a new write instruction for the given motor ID, control table address and value.
+	pub fn new(packet_id: u8, address: u16, value: T) -> Self {
+		Self { packet_id, address, value }
+	}
+}
+
+impl<T: Value> Instruction for Write<T> {
+	type Response = ();
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::WRITE
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		2 + T::ENCODED_LEN
+	}
+
+	fn encode_parameters(&self, buffer: &mut [u8]) {
+		write_u16_le(buffer, self.address);
+		self.value.encode(&mut buffer[2..]);
+	}
+
+	fn parse_response<R: StatusResponse, E>(&self, _response: &R) -> Result<Self::Response, ReadError<E>> {
+		Ok(())
+	}
+}
+
+/// Write a value to a motor's control table, deferred until an [`Action`] instruction is sent.
+pub struct RegWrite<T> {
+	packet_id: u8,
+	address: u16,
+	value: T,
+}
+
+impl<T: Value> RegWrite<T> {
+	/// Create a new reg-write instruction for the given motor ID, control table address and value.
+	pub fn new(packet_id: u8, address: u16, value: T) -> Self {
+		Self { packet_id, address, value }
+	}
+}
+
+impl<T: Value> Instruction for RegWrite<T> {
+	type Response = ();
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::REG_WRITE
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		2 + T::ENCODED_LEN
+	}
+
+	fn encode_parameters(&self, buffer: &mut [u8]) {
+		write_u16_le(buffer, self.address);
+		self.value.encode(&mut buffer[2..]);
+	}
+
+	fn parse_response<R: StatusResponse, E>(&self, _response: &R) -> Result<Self::Response, ReadError<E>> {
+		Ok(())
+	}
+}
+
+/// Trigger all pending [`RegWrite`] instructions on a motor.
+pub struct Action {
+	packet_id: u8,
+}
+
+impl Action {
+	/// Create a new action instruction for the given motor ID.
+	pub fn new(packet_id: u8) -> Self {
+		Self { packet_id }
+	}
+}
+
+impl Instruction for Action {
+	type Response = ();
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::ACTION
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		0
+	}
+
+	fn encode_parameters(&self, _buffer: &mut [u8]) {}
+
+	fn parse_response<R: StatusResponse, E>(&self, _response: &R) -> Result<Self::Response, ReadError<E>> {
+		Ok(())
+	}
+}
+
+/// What to reset when sending a [`FactoryReset`] instruction.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FactoryResetKind {
+	/// Reset everything except the ID.
+	ExceptId = 0x01,
+	/// Reset everything except the ID and the baud rate.
+	ExceptIdAndBaudRate = 0x02,
+	/// Reset everything, including the ID and the baud rate.
+	All = 0xFF,
+}
+
+/// Reset a motor's control table to its factory defaults.
+pub struct FactoryReset {
+	packet_id: u8,
+	kind: FactoryResetKind,
+}
+
+impl FactoryReset {
+	/// Create a new factory-reset instruction for the given motor ID.
+	pub fn new(packet_id: u8, kind: FactoryResetKind) -> Self {
+		Self { packet_id, kind }
+	}
+}
+
+impl Instruction for FactoryReset {
+	type Response = ();
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::FACTORY_RESET
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		1
+	}
+
+	fn encode_parameters(&self, buffer: &mut [u8]) {
+		buffer[0] = self.kind as u8;
+	}
+
+	fn parse_response<R: StatusResponse, E>(&self, _response: &R) -> Result<Self::Response, ReadError<E>> {
+		Ok(())
+	}
+}
+
+/// Reboot a motor.
+pub struct Reboot {
+	packet_id: u8,
+}
+
+impl Reboot {
+	/// Create a new reboot instruction for the given motor ID.
+	pub fn new(packet_id: u8) -> Self {
+		Self { packet_id }
+	}
+}
+
+impl Instruction for Reboot {
+	type Response = ();
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::REBOOT
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		0
+	}
+
+	fn encode_parameters(&self, _buffer: &mut [u8]) {}
+
+	fn parse_response<R: StatusResponse, E>(&self, _response: &R) -> Result<Self::Response, ReadError<E>> {
+		Ok(())
+	}
+}
+
+/// The magic pattern required by the [`Clear`] instruction, to guard against accidental resets.
+const CLEAR_MAGIC: [u8; 4] = [0x44, 0x58, 0x4C, 0x22];
+
+/// Clear a motor's multi-turn revolution counter.
+pub struct Clear {
+	packet_id: u8,
+}
+
+impl Clear {
+	/// Create a new clear instruction for the given motor ID.
+	pub fn new(packet_id: u8) -> Self {
+		Self { packet_id }
+	}
+}
+
+impl Instruction for Clear {
+	type Response = ();
+
+	fn packet_id(&self) -> u8 {
+		self.packet_id
+	}
+
+	fn instruction_id(&self) -> u8 {
+		instruction_id::CLEAR
+	}
+
+	fn request_parameters_len(&self) -> usize {
+		5
+	}
+
+	fn encode_parameters(&self, buffer: &mut [u8]) {
+		buffer[0] = 0x01; // Sub-command: reset the multi-turn revolution counter.
+		buffer[1..5].copy_from_slice(&CLEAR_MAGIC);
+	}
+
+	fn parse_response<R: StatusResponse, E>(&self, _response: &R) -> Result<Self::Response, ReadError<E>> {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+	use assert2::assert;
+
+	use super::*;
+
+	/// A canned [`StatusResponse`] for feeding synthetic replies to [`Instruction::parse_response`].
+	struct TestResponse {
+		packet_id: u8,
+		instruction_id: u8,
+		error: u8,
+		parameters: Vec<u8>,
+	}
+
+	impl StatusResponse for TestResponse {
+		fn packet_id(&self) -> u8 {
+			self.packet_id
+		}
+
+		fn instruction_id(&self) -> u8 {
+			self.instruction_id
+		}
+
+		fn error(&self) -> u8 {
+			self.error
+		}
+
+		fn parameters(&self) -> &[u8] {
+			&self.parameters
+		}
+	}
+
+	fn status_response(parameters: &[u8]) -> TestResponse {
+		TestResponse {
+			packet_id: 1,
+			instruction_id: instruction_id::STATUS,
+			error: 0,
+			parameters: parameters.to_vec(),
+		}
+	}
+
+	/// Encode `instruction` into a freshly allocated buffer of its declared length.
+	fn encode<I: Instruction>(instruction: &I) -> Vec<u8> {
+		let mut buffer = vec![0; instruction.request_parameters_len()];
+		instruction.encode_parameters(&mut buffer);
+		buffer
+	}
+
+	#[test]
+	fn test_ping_roundtrip() {
+		let instruction = Ping::new(5);
+		assert!(instruction.packet_id() == 5);
+		assert!(instruction.instruction_id() == instruction_id::PING);
+		assert!(encode(&instruction) == []);
+
+		let response = instruction.parse_response::<_, ()>(&status_response(&[0x34, 0x12, 7])).unwrap();
+		assert!(response == PingResponse { model_number: 0x1234, firmware_version: 7 });
+	}
+
+	#[test]
+	fn test_ping_response_invalid_parameter_count() {
+		let instruction = Ping::new(5);
+		let result = instruction.parse_response::<_, ()>(&status_response(&[0x34, 0x12]));
+		assert!(let Err(ReadError::InvalidParameterCount(_)) = result);
+	}
+
+	#[test]
+	fn test_read_roundtrip() {
+		let instruction = Read::<u16>::new(5, 0x002A);
+		assert!(instruction.instruction_id() == instruction_id::READ);
+		assert!(encode(&instruction) == [0x2A, 0x00, 0x02, 0x00]);
+
+		let value = instruction.parse_response::<_, ()>(&status_response(&[0x34, 0x12])).unwrap();
+		assert!(value == 0x1234);
+	}
+
+	#[test]
+	fn test_write_roundtrip() {
+		let instruction = Write::new(5, 0x002A, 0x1234u16);
+		assert!(instruction.instruction_id() == instruction_id::WRITE);
+		assert!(encode(&instruction) == [0x2A, 0x00, 0x34, 0x12]);
+
+		instruction.parse_response::<_, ()>(&status_response(&[])).unwrap();
+	}
+
+	#[test]
+	fn test_reg_write_roundtrip() {
+		let instruction = RegWrite::new(5, 0x002A, 0x1234u16);
+		assert!(instruction.instruction_id() == instruction_id::REG_WRITE);
+		assert!(encode(&instruction) == [0x2A, 0x00, 0x34, 0x12]);
+
+		instruction.parse_response::<_, ()>(&status_response(&[])).unwrap();
+	}
+
+	#[test]
+	fn test_action_roundtrip() {
+		let instruction = Action::new(5);
+		assert!(instruction.instruction_id() == instruction_id::ACTION);
+		assert!(encode(&instruction) == []);
+
+		instruction.parse_response::<_, ()>(&status_response(&[])).unwrap();
+	}
+
+	#[test]
+	fn test_factory_reset_roundtrip() {
+		let instruction = FactoryReset::new(5, FactoryResetKind::ExceptIdAndBaudRate);
+		assert!(instruction.instruction_id() == instruction_id::FACTORY_RESET);
+		assert!(encode(&instruction) == [0x02]);
+
+		instruction.parse_response::<_, ()>(&status_response(&[])).unwrap();
+	}
+
+	#[test]
+	fn test_reboot_roundtrip() {
+		let instruction = Reboot::new(5);
+		assert!(instruction.instruction_id() == instruction_id::REBOOT);
+		assert!(encode(&instruction) == []);
+
+		instruction.parse_response::<_, ()>(&status_response(&[])).unwrap();
+	}
+
+	#[test]
+	fn test_clear_roundtrip() {
+		let instruction = Clear::new(5);
+		assert!(instruction.instruction_id() == instruction_id::CLEAR);
+		assert!(encode(&instruction) == [0x01, 0x44, 0x58, 0x4C, 0x22]);
+
+		instruction.parse_response::<_, ()>(&status_response(&[])).unwrap();
+	}
+}
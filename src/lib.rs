@@ -0,0 +1,44 @@
+//! Pure Rust implementation of the Dynamixel Protocol 2.0.
+//!
+//! With the default `std` feature, this crate lets you talk to Dynamixel servos over a serial
+//! port (or anything else implementing [`std::io::Read`] and [`std::io::Write`]). Disable default
+//! features and implement [`transport::ProtoRead`]/[`transport::ProtoWrite`] and [`clock::Clock`]
+//! yourself to run the same framing and byte-stuffing code on a `no_std` target. Enable the
+//! `tokio` feature for [`AsyncBus`], an async counterpart built on `tokio::io::{AsyncRead, AsyncWrite}`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// A tiny, always-available tracing macro so the rest of the crate doesn't have to
+// sprinkle `#[cfg(feature = "log")]` everywhere it wants to log something.
+macro_rules! trace {
+	($($args:tt)*) => {
+		#[cfg(feature = "log")]
+		log::trace!($($args)*);
+	};
+}
+
+mod buffer;
+mod bus;
+#[cfg(feature = "tokio")]
+mod bus_async;
+mod bytestuff;
+mod checksum;
+pub mod clock;
+mod endian;
+mod error;
+mod framing;
+pub mod instructions;
+pub mod transport;
+
+pub use buffer::GrowableBuffer;
+pub use bus::{Bus, Response};
+#[cfg(feature = "std")]
+pub use bus::SyncReadResults;
+#[cfg(feature = "tokio")]
+pub use bus_async::{AsyncBus, AsyncResponse};
+pub use clock::Clock;
+pub use error::{InvalidChecksum, InvalidInstruction, InvalidMessageLength, InvalidPacketId, InvalidParameterCount, MotorError, ReadError, TransferError, WriteError};
+pub use transport::{ProtoRead, ProtoWrite, Transport};
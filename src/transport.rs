@@ -0,0 +1,116 @@
+//! Abstraction over the communication stream, so the rest of the crate can run without `std`.
+//!
+//! [`Bus`](crate::Bus) no longer talks to [`std::io::Read`]/[`std::io::Write`] directly: instead it
+//! talks to anything implementing [`ProtoRead`] and [`ProtoWrite`], which lets the same framing and
+//! byte-stuffing code run on bare-metal targets driving DYNAMIXELs over an MCU UART.
+
+/// The readable half of a transport used to communicate with Dynamixel motors.
+pub trait ProtoRead {
+	/// The error type produced by this transport.
+	type Error;
+
+	/// Read into `buffer`, returning the number of bytes read.
+	///
+	/// A return value of `0` means "no data available right now", not end-of-stream:
+	/// unlike [`std::io::Read::read`], callers are expected to retry until their own deadline passes.
+	fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// The writable half of a transport used to communicate with Dynamixel motors.
+pub trait ProtoWrite {
+	/// The error type produced by this transport.
+	type Error;
+
+	/// Write all of `buffer` to the transport.
+	fn write_all(&mut self, buffer: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// A full-duplex transport used to communicate with Dynamixel motors.
+///
+/// This is automatically implemented for anything that implements both [`ProtoRead`] and [`ProtoWrite`]
+/// with the same error type, so you only need to implement those two traits.
+pub trait Transport: ProtoRead<Error = <Self as Transport>::Error> + ProtoWrite<Error = <Self as Transport>::Error> {
+	/// The error type produced by this transport.
+	type Error;
+}
+
+impl<T, E> Transport for T
+where
+	T: ProtoRead<Error = E> + ProtoWrite<Error = E>,
+{
+	type Error = E;
+}
+
+/// Blanket [`ProtoRead`]/[`ProtoWrite`] implementation for any [`std::io::Read`] + [`std::io::Write`] stream.
+///
+/// This is what lets [`Bus::new`](crate::Bus::new) accept a plain serial port: with the default `std`
+/// feature enabled, every such stream already satisfies [`Transport`].
+#[cfg(feature = "std")]
+mod std_impl {
+	use super::{ProtoRead, ProtoWrite};
+
+	impl<T: std::io::Read> ProtoRead for T {
+		type Error = std::io::Error;
+
+		fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+			std::io::Read::read(self, buffer)
+		}
+	}
+
+	impl<T: std::io::Write> ProtoWrite for T {
+		type Error = std::io::Error;
+
+		fn write_all(&mut self, buffer: &[u8]) -> Result<(), Self::Error> {
+			std::io::Write::write_all(self, buffer)
+		}
+	}
+}
+
+/// [`ProtoRead`]/[`ProtoWrite`] implementation for `embedded-hal` serial devices.
+#[cfg(feature = "embedded-hal")]
+mod embedded_hal_impl {
+	use super::{ProtoRead, ProtoWrite};
+	use embedded_hal::serial::{Read as SerialRead, Write as SerialWrite};
+	use nb::block;
+
+	/// Wraps an `embedded-hal` serial port so it can be used as a [`Transport`](super::Transport).
+	pub struct EmbeddedHalTransport<T> {
+		inner: T,
+	}
+
+	impl<T> EmbeddedHalTransport<T> {
+		/// Wrap an `embedded-hal` serial port.
+		pub fn new(inner: T) -> Self {
+			Self { inner }
+		}
+	}
+
+	impl<T: SerialRead<u8>> ProtoRead for EmbeddedHalTransport<T> {
+		type Error = T::Error;
+
+		fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+			for (count, byte) in buffer.iter_mut().enumerate() {
+				match self.inner.read() {
+					Ok(value) => *byte = value,
+					Err(nb::Error::WouldBlock) => return Ok(count),
+					Err(nb::Error::Other(e)) => return Err(e),
+				}
+			}
+			Ok(buffer.len())
+		}
+	}
+
+	impl<T: SerialWrite<u8>> ProtoWrite for EmbeddedHalTransport<T> {
+		type Error = T::Error;
+
+		fn write_all(&mut self, buffer: &[u8]) -> Result<(), Self::Error> {
+			for &byte in buffer {
+				block!(self.inner.write(byte))?;
+			}
+			Ok(())
+		}
+	}
+}
+
+#[cfg(feature = "embedded-hal")]
+pub use embedded_hal_impl::EmbeddedHalTransport;